@@ -0,0 +1,204 @@
+//! `candles` subcommand: aggregate the live tick stream into fixed-interval OHLCV bars.
+
+use crate::OutputFormat;
+use clap::Args;
+use csv::Writer;
+use ibapi::client::blocking::Client;
+use ibapi::prelude::*;
+use serde::Serialize;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Args)]
+pub struct CandlesArgs {
+    /// Symbol to aggregate
+    #[arg(short, long, default_value = "AAPL")]
+    pub symbol: String,
+
+    /// Bar interval, e.g. "1m", "5m", "1h"
+    #[arg(long, default_value = "1m")]
+    pub interval: String,
+}
+
+#[derive(Clone, Serialize)]
+struct Candle {
+    symbol: String,
+    start: u64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+pub fn run(client: &Client, args: &CandlesArgs, format: OutputFormat) {
+    let interval_secs = parse_interval(&args.interval);
+    let contract = Contract::stock(&args.symbol).build();
+
+    let subscription = match client.market_data(&contract).subscribe() {
+        Ok(subscription) => subscription,
+        Err(e) => {
+            eprintln!("Failed to subscribe to market data: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut bucket: Option<Candle> = None;
+    let mut bucket_key: Option<u64> = None;
+
+    for tick in &subscription {
+        match tick {
+            TickTypes::Price(price) if is_trade_price(price.tick_type) => {
+                let key = bucket_key_now(interval_secs);
+                flush_if_new_bucket(&mut bucket, &mut bucket_key, key, format);
+
+                let candle = bucket.get_or_insert_with(|| Candle {
+                    symbol: args.symbol.clone(),
+                    start: key * interval_secs,
+                    open: price.price,
+                    high: price.price,
+                    low: price.price,
+                    close: price.price,
+                    volume: 0.0,
+                });
+                candle.high = candle.high.max(price.price);
+                candle.low = candle.low.min(price.price);
+                candle.close = price.price;
+            }
+            TickTypes::Size(size) if is_trade_size(size.tick_type) => {
+                let key = bucket_key_now(interval_secs);
+                flush_if_new_bucket(&mut bucket, &mut bucket_key, key, format);
+
+                if let Some(candle) = bucket.as_mut() {
+                    candle.volume += size.size;
+                }
+            }
+            TickTypes::SnapshotEnd => break,
+            _ => {}
+        }
+    }
+
+    if let Some(candle) = bucket.take() {
+        emit(&candle, format);
+    }
+}
+
+fn flush_if_new_bucket(
+    bucket: &mut Option<Candle>,
+    bucket_key: &mut Option<u64>,
+    key: u64,
+    format: OutputFormat,
+) {
+    match *bucket_key {
+        Some(existing) if existing == key => {}
+        Some(_) => {
+            if let Some(candle) = bucket.take() {
+                emit(&candle, format);
+            }
+            *bucket_key = Some(key);
+        }
+        None => {
+            *bucket_key = Some(key);
+        }
+    }
+}
+
+/// The live tick stream carries no per-tick timestamp in this API, so the
+/// bucket key is derived from local receipt time rather than exchange time.
+fn bucket_key_now(interval_secs: u64) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    now / interval_secs
+}
+
+/// `Close` is the prior session's static closing price delivered once on
+/// subscribe, not a live trade - only `Last` reflects an actual execution.
+fn is_trade_price(tick_type: TickType) -> bool {
+    matches!(tick_type, TickType::Last)
+}
+
+fn is_trade_size(tick_type: TickType) -> bool {
+    matches!(tick_type, TickType::LastSize)
+}
+
+fn parse_interval(interval: &str) -> u64 {
+    let interval = interval.trim();
+    let (value, unit) = interval.split_at(interval.len() - 1);
+    let value: u64 = value.parse().unwrap_or(1);
+    match unit {
+        "s" => value,
+        "h" => value * 3600,
+        _ => value * 60,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close: f64) -> Candle {
+        Candle {
+            symbol: "AAPL".to_string(),
+            start: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0.0,
+        }
+    }
+
+    #[test]
+    fn same_bucket_key_does_not_flush() {
+        let mut bucket = Some(candle(150.0));
+        let mut bucket_key = Some(5);
+        flush_if_new_bucket(&mut bucket, &mut bucket_key, 5, OutputFormat::Json);
+        assert_eq!(bucket_key, Some(5));
+        assert!(bucket.is_some());
+    }
+
+    #[test]
+    fn new_bucket_key_flushes_existing_candle() {
+        let mut bucket = Some(candle(150.0));
+        let mut bucket_key = Some(5);
+        flush_if_new_bucket(&mut bucket, &mut bucket_key, 6, OutputFormat::Json);
+        assert_eq!(bucket_key, Some(6));
+        assert!(bucket.is_none());
+    }
+
+    #[test]
+    fn first_tick_sets_bucket_key_without_flushing() {
+        let mut bucket = None;
+        let mut bucket_key = None;
+        flush_if_new_bucket(&mut bucket, &mut bucket_key, 5, OutputFormat::Json);
+        assert_eq!(bucket_key, Some(5));
+        assert!(bucket.is_none());
+    }
+
+    #[test]
+    fn parses_interval_suffixes() {
+        assert_eq!(parse_interval("30s"), 30);
+        assert_eq!(parse_interval("1m"), 60);
+        assert_eq!(parse_interval("5m"), 300);
+        assert_eq!(parse_interval("1h"), 3600);
+    }
+}
+
+fn emit(candle: &Candle, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(candle).unwrap()),
+        OutputFormat::Csv => {
+            let mut wtr = Writer::from_writer(io::stdout());
+            wtr.serialize(candle).unwrap();
+            wtr.flush().unwrap();
+        }
+        OutputFormat::Text | OutputFormat::Ledger | OutputFormat::Beancount => {
+            println!(
+                "{} {}: O={:.2} H={:.2} L={:.2} C={:.2} V={:.0}",
+                candle.start, candle.symbol, candle.open, candle.high, candle.low, candle.close, candle.volume
+            );
+        }
+    }
+}
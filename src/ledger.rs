@@ -0,0 +1,209 @@
+//! Plain-text accounting export for executions (ledger-cli and beancount).
+
+use crate::ExecutionRow;
+use std::collections::HashMap;
+
+/// One posting within a transaction. `unit_cost` is set only on the traded
+/// asset's own leg, carrying the `@ price currency` (ledger) / `{price
+/// currency}` (beancount) cost annotation needed for the transaction to
+/// balance across commodities.
+struct Posting {
+    account: String,
+    quantity: String,
+    commodity: String,
+    unit_cost: Option<(f64, String)>,
+}
+
+struct Transaction {
+    date: String,
+    narration: String,
+    postings: Vec<Posting>,
+}
+
+/// Running average-cost book per symbol: (total shares held, total cost basis).
+#[derive(Default)]
+struct CostBasis {
+    shares: f64,
+    cost: f64,
+}
+
+/// Render executions as ledger-cli formatted transactions.
+pub fn render_ledger(executions: &[ExecutionRow]) -> String {
+    let mut out = String::new();
+    for txn in build_transactions(executions) {
+        out.push_str(&format!("{} * \"{}\"\n", txn.date, txn.narration));
+        for posting in &txn.postings {
+            let amount = match &posting.unit_cost {
+                Some((price, currency)) => format!(
+                    "{} {} @ {:.2} {}",
+                    posting.quantity, posting.commodity, price, currency
+                ),
+                None => format!("{} {}", posting.quantity, posting.commodity),
+            };
+            out.push_str(&format!("    {:<40}{}\n", posting.account, amount));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Render executions as beancount formatted transactions.
+pub fn render_beancount(executions: &[ExecutionRow]) -> String {
+    let mut out = String::new();
+    for txn in build_transactions(executions) {
+        out.push_str(&format!("{} * \"{}\"\n", txn.date, txn.narration));
+        for posting in &txn.postings {
+            let amount = match &posting.unit_cost {
+                Some((price, currency)) => format!(
+                    "{} {} {{{:.2} {}}}",
+                    posting.quantity, posting.commodity, price, currency
+                ),
+                None => format!("{} {}", posting.quantity, posting.commodity),
+            };
+            out.push_str(&format!("  {:<38}{}\n", posting.account, amount));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn build_transactions(executions: &[ExecutionRow]) -> Vec<Transaction> {
+    let mut books: HashMap<String, CostBasis> = HashMap::new();
+    let mut transactions = Vec::with_capacity(executions.len());
+
+    for exec in executions {
+        let asset_account = format!("Assets:Broker:IBKR:{}", exec.symbol);
+        let cash_account = "Assets:Broker:IBKR:Cash".to_string();
+        let commission_account = "Expenses:Broker:Commissions".to_string();
+        let book = books.entry(exec.symbol.clone()).or_default();
+
+        let mut postings = Vec::new();
+        let narration = format!("{} {} {}", exec.side, fmt_qty(exec.shares), exec.symbol);
+
+        if exec.side == "BUY" {
+            let cost = exec.shares * exec.price + exec.commission;
+            postings.push(Posting {
+                account: asset_account,
+                quantity: fmt_qty(exec.shares),
+                commodity: exec.symbol.clone(),
+                unit_cost: Some((exec.price, exec.currency.clone())),
+            });
+            postings.push(Posting {
+                account: commission_account,
+                quantity: format!("{:.2}", exec.commission),
+                commodity: exec.currency.clone(),
+                unit_cost: None,
+            });
+            postings.push(Posting {
+                account: cash_account,
+                quantity: format!("{:.2}", -cost),
+                commodity: exec.currency.clone(),
+                unit_cost: None,
+            });
+
+            book.shares += exec.shares;
+            book.cost += cost;
+        } else {
+            let avg_cost = if book.shares > 0.0 {
+                book.cost / book.shares
+            } else {
+                exec.price
+            };
+            let cost_basis = avg_cost * exec.shares;
+            let gross_proceeds = exec.shares * exec.price;
+            let net_proceeds = gross_proceeds - exec.commission;
+            let realized_gain = gross_proceeds - cost_basis;
+
+            postings.push(Posting {
+                account: asset_account,
+                quantity: fmt_qty(-exec.shares),
+                commodity: exec.symbol.clone(),
+                unit_cost: Some((avg_cost, exec.currency.clone())),
+            });
+            postings.push(Posting {
+                account: cash_account,
+                quantity: format!("{:.2}", net_proceeds),
+                commodity: exec.currency.clone(),
+                unit_cost: None,
+            });
+            postings.push(Posting {
+                account: commission_account,
+                quantity: format!("{:.2}", exec.commission),
+                commodity: exec.currency.clone(),
+                unit_cost: None,
+            });
+            postings.push(Posting {
+                account: "Income:Capital-Gains".to_string(),
+                quantity: format!("{:.2}", -realized_gain),
+                commodity: exec.currency.clone(),
+                unit_cost: None,
+            });
+
+            book.shares -= exec.shares;
+            book.cost -= cost_basis;
+        }
+
+        transactions.push(Transaction {
+            date: exec.date.clone(),
+            narration,
+            postings,
+        });
+    }
+
+    transactions
+}
+
+fn fmt_qty(qty: f64) -> String {
+    if qty.fract() == 0.0 {
+        format!("{}", qty as i64)
+    } else {
+        format!("{:.4}", qty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(side: &str, shares: f64, price: f64, commission: f64) -> ExecutionRow {
+        ExecutionRow {
+            account: "U123".to_string(),
+            symbol: "AAPL".to_string(),
+            side: side.to_string(),
+            shares,
+            price,
+            commission,
+            currency: "USD".to_string(),
+            date: "2026-07-26".to_string(),
+            exec_id: "e1".to_string(),
+        }
+    }
+
+    /// The USD-equivalent value of a posting: quantity * unit price for the
+    /// traded asset leg, or the bare quantity for a currency-denominated leg.
+    fn posting_value(posting: &Posting) -> f64 {
+        let quantity: f64 = posting.quantity.parse().unwrap();
+        match &posting.unit_cost {
+            Some((price, _)) => quantity * price,
+            None => quantity,
+        }
+    }
+
+    #[test]
+    fn buy_postings_balance_to_zero() {
+        let transactions = build_transactions(&[exec("BUY", 100.0, 150.0, 1.0)]);
+        let total: f64 = transactions[0].postings.iter().map(posting_value).sum();
+        assert!(total.abs() < 1e-6, "unbalanced transaction: {}", total);
+    }
+
+    #[test]
+    fn sell_postings_balance_to_zero() {
+        let executions = vec![
+            exec("BUY", 100.0, 150.0, 1.0),
+            exec("SELL", 40.0, 160.0, 1.0),
+        ];
+        let transactions = build_transactions(&executions);
+        let total: f64 = transactions[1].postings.iter().map(posting_value).sum();
+        assert!(total.abs() < 1e-6, "unbalanced transaction: {}", total);
+    }
+}
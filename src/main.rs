@@ -1,20 +1,31 @@
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 use csv::Writer;
 use ibapi::accounts::{
     types::AccountGroup, AccountSummaryResult, AccountSummaryTags, PositionUpdate,
 };
 use ibapi::client::blocking::Client;
+use ibapi::orders::{ExecutionFilter, Executions};
 use ibapi::prelude::*;
 use serde::Serialize;
 use std::env;
 use std::io;
 
+mod candles;
+mod ledger;
+mod order;
+mod pool;
+mod server;
+mod watch;
+
 #[derive(Parser)]
 #[command(name = "plat-ibkr")]
 #[command(about = "IBKR trading platform CLI")]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Output format
-    #[arg(short, long, value_enum, default_value = "text")]
+    #[arg(short, long, value_enum, default_value = "text", global = true)]
     format: OutputFormat,
 
     /// Symbol for market data (default: AAPL)
@@ -24,6 +35,23 @@ struct Cli {
     /// Skip market data request
     #[arg(long)]
     no_market_data: bool,
+
+    /// Keep running, re-collecting and emitting data every INTERVAL seconds
+    /// instead of connecting once and exiting
+    #[arg(long, value_name = "INTERVAL")]
+    watch: Option<u64>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Place an order and stream fills until filled, partially filled, or cancelled
+    Order(order::OrderArgs),
+
+    /// Aggregate the live tick stream into fixed-interval OHLCV candles
+    Candles(candles::CandlesArgs),
+
+    /// Broadcast account, position, and market-data updates over WebSocket
+    Serve(server::ServeArgs),
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
@@ -31,9 +59,13 @@ enum OutputFormat {
     Text,
     Json,
     Csv,
+    /// Plain-text accounting (ledger-cli) export of executions.
+    Ledger,
+    /// Beancount export of executions.
+    Beancount,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 struct AccountSummaryRow {
     account: String,
     tag: String,
@@ -41,7 +73,7 @@ struct AccountSummaryRow {
     currency: String,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 struct PositionRow {
     account: String,
     symbol: String,
@@ -50,7 +82,7 @@ struct PositionRow {
     market_value: f64,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 struct MarketDataRow {
     symbol: String,
     tick_type: String,
@@ -64,6 +96,19 @@ struct OutputData {
     market_data: Vec<MarketDataRow>,
 }
 
+#[derive(Clone, Serialize)]
+pub(crate) struct ExecutionRow {
+    account: String,
+    symbol: String,
+    side: String,
+    shares: f64,
+    price: f64,
+    commission: f64,
+    currency: String,
+    date: String,
+    exec_id: String,
+}
+
 fn main() {
     env_logger::init();
     let cli = Cli::parse();
@@ -76,6 +121,11 @@ fn main() {
         eprintln!("Connecting to TWS/Gateway at {}...", connection_url);
     }
 
+    if let (None, Some(interval)) = (&cli.command, cli.watch) {
+        let pool = pool::ClientPool::connect(&connection_url, 100);
+        watch::run(&pool, &cli.symbol, cli.no_market_data, cli.format, interval);
+    }
+
     let client = match Client::connect(&connection_url, 100) {
         Ok(c) => {
             if cli.format == OutputFormat::Text {
@@ -92,6 +142,33 @@ fn main() {
         }
     };
 
+    match cli.command {
+        Some(Command::Order(args)) => {
+            order::run(&client, &args, cli.format);
+            return;
+        }
+        Some(Command::Candles(args)) => {
+            candles::run(&client, &args, cli.format);
+            return;
+        }
+        Some(Command::Serve(args)) => {
+            server::run(client, &args);
+            return;
+        }
+        None => {}
+    }
+
+    if matches!(cli.format, OutputFormat::Ledger | OutputFormat::Beancount) {
+        let mut executions = Vec::new();
+        collect_executions(&client, &mut executions);
+        match cli.format {
+            OutputFormat::Ledger => print!("{}", ledger::render_ledger(&executions)),
+            OutputFormat::Beancount => print!("{}", ledger::render_beancount(&executions)),
+            _ => unreachable!(),
+        }
+        return;
+    }
+
     let mut data = OutputData {
         account_summary: Vec::new(),
         positions: Vec::new(),
@@ -114,10 +191,14 @@ fn main() {
         OutputFormat::Text => print_text(&data, &cli.symbol),
         OutputFormat::Json => print_json(&data),
         OutputFormat::Csv => print_csv(&data),
+        OutputFormat::Ledger | OutputFormat::Beancount => unreachable!(),
     }
 }
 
-fn collect_account_summary(client: &Client, rows: &mut Vec<AccountSummaryRow>) {
+/// Returns whether the account-summary subscription was established at all,
+/// so callers (notably `--watch`) can tell a dropped socket apart from an
+/// account that legitimately has nothing to report.
+fn collect_account_summary(client: &Client, rows: &mut Vec<AccountSummaryRow>) -> bool {
     let tags = &[
         AccountSummaryTags::ACCOUNT_TYPE,
         AccountSummaryTags::NET_LIQUIDATION,
@@ -127,41 +208,83 @@ fn collect_account_summary(client: &Client, rows: &mut Vec<AccountSummaryRow>) {
         AccountSummaryTags::AVAILABLE_FUNDS,
     ];
 
-    if let Ok(subscription) = client.account_summary(&AccountGroup("All".to_string()), tags) {
-        for update in &subscription {
-            match update {
-                AccountSummaryResult::Summary(summary) => {
-                    rows.push(AccountSummaryRow {
-                        account: summary.account.clone(),
-                        tag: summary.tag.clone(),
-                        value: summary.value.clone(),
-                        currency: summary.currency.clone(),
-                    });
-                }
-                AccountSummaryResult::End => {
-                    subscription.cancel();
-                    break;
-                }
+    let Ok(subscription) = client.account_summary(&AccountGroup("All".to_string()), tags) else {
+        return false;
+    };
+
+    for update in &subscription {
+        match update {
+            AccountSummaryResult::Summary(summary) => {
+                rows.push(AccountSummaryRow {
+                    account: summary.account.clone(),
+                    tag: summary.tag.clone(),
+                    value: summary.value.clone(),
+                    currency: summary.currency.clone(),
+                });
+            }
+            AccountSummaryResult::End => {
+                subscription.cancel();
+                break;
+            }
+        }
+    }
+    true
+}
+
+/// Returns whether the positions subscription was established at all; see
+/// `collect_account_summary` for why callers care.
+fn collect_positions(client: &Client, rows: &mut Vec<PositionRow>) -> bool {
+    let Ok(positions) = client.positions() else {
+        return false;
+    };
+
+    while let Some(update) = positions.next() {
+        match update {
+            PositionUpdate::Position(pos) => {
+                rows.push(PositionRow {
+                    account: pos.account.clone(),
+                    symbol: pos.contract.symbol.to_string(),
+                    position: pos.position,
+                    average_cost: pos.average_cost,
+                    market_value: pos.position * pos.average_cost,
+                });
+            }
+            PositionUpdate::PositionEnd => {
+                positions.cancel();
+                break;
             }
         }
     }
+    true
 }
 
-fn collect_positions(client: &Client, rows: &mut Vec<PositionRow>) {
-    if let Ok(positions) = client.positions() {
-        while let Some(update) = positions.next() {
+fn collect_executions(client: &Client, rows: &mut Vec<ExecutionRow>) {
+    if let Ok(subscription) = client.executions(&ExecutionFilter::default()) {
+        for update in &subscription {
             match update {
-                PositionUpdate::Position(pos) => {
-                    rows.push(PositionRow {
-                        account: pos.account.clone(),
-                        symbol: pos.contract.symbol.to_string(),
-                        position: pos.position,
-                        average_cost: pos.average_cost,
-                        market_value: pos.position * pos.average_cost,
+                Executions::ExecutionData(exec) => {
+                    rows.push(ExecutionRow {
+                        account: exec.execution.account_number.clone(),
+                        symbol: exec.contract.symbol.to_string(),
+                        side: normalize_side(&exec.execution.side),
+                        shares: exec.execution.shares,
+                        price: exec.execution.price,
+                        commission: 0.0,
+                        currency: exec.contract.currency.clone(),
+                        date: format_exec_date(&exec.execution.time),
+                        exec_id: exec.execution.execution_id.clone(),
                     });
                 }
-                PositionUpdate::PositionEnd => {
-                    positions.cancel();
+                Executions::CommissionReport(report) => {
+                    if let Some(row) = rows
+                        .iter_mut()
+                        .find(|r| r.exec_id == report.execution_id)
+                    {
+                        row.commission = report.commission;
+                    }
+                }
+                Executions::End => {
+                    subscription.cancel();
                     break;
                 }
             }
@@ -169,38 +292,62 @@ fn collect_positions(client: &Client, rows: &mut Vec<PositionRow>) {
     }
 }
 
-fn collect_market_data(client: &Client, symbol: &str, rows: &mut Vec<MarketDataRow>) {
+/// IBKR reports fill side as "BOT"/"SLD"; normalize to BUY/SELL for the ledger export.
+fn normalize_side(side: &str) -> String {
+    match side {
+        "SLD" => "SELL".to_string(),
+        _ => "BUY".to_string(),
+    }
+}
+
+/// IBKR execution timestamps look like "20260726  10:00:00"; ledger/beancount
+/// transactions only need the date component.
+fn format_exec_date(time: &str) -> String {
+    let digits = time.trim();
+    if digits.len() >= 8 {
+        format!("{}-{}-{}", &digits[0..4], &digits[4..6], &digits[6..8])
+    } else {
+        digits.to_string()
+    }
+}
+
+/// Returns whether the market-data subscription was established at all; see
+/// `collect_account_summary` for why callers care.
+fn collect_market_data(client: &Client, symbol: &str, rows: &mut Vec<MarketDataRow>) -> bool {
     let contract = Contract::stock(symbol).build();
 
-    if let Ok(subscription) = client.market_data(&contract).snapshot().subscribe() {
-        for tick in &subscription {
-            match tick {
-                TickTypes::Price(price) => {
-                    rows.push(MarketDataRow {
-                        symbol: symbol.to_string(),
-                        tick_type: format!("{:?}", price.tick_type),
-                        value: price.price,
-                    });
-                }
-                TickTypes::Size(size) => {
-                    rows.push(MarketDataRow {
-                        symbol: symbol.to_string(),
-                        tick_type: format!("{:?}", size.tick_type),
-                        value: size.size,
-                    });
-                }
-                TickTypes::PriceSize(ps) => {
-                    rows.push(MarketDataRow {
-                        symbol: symbol.to_string(),
-                        tick_type: format!("{:?}", ps.price_tick_type),
-                        value: ps.price,
-                    });
-                }
-                TickTypes::SnapshotEnd => break,
-                _ => {}
+    let Ok(subscription) = client.market_data(&contract).snapshot().subscribe() else {
+        return false;
+    };
+
+    for tick in &subscription {
+        match tick {
+            TickTypes::Price(price) => {
+                rows.push(MarketDataRow {
+                    symbol: symbol.to_string(),
+                    tick_type: format!("{:?}", price.tick_type),
+                    value: price.price,
+                });
+            }
+            TickTypes::Size(size) => {
+                rows.push(MarketDataRow {
+                    symbol: symbol.to_string(),
+                    tick_type: format!("{:?}", size.tick_type),
+                    value: size.size,
+                });
+            }
+            TickTypes::PriceSize(ps) => {
+                rows.push(MarketDataRow {
+                    symbol: symbol.to_string(),
+                    tick_type: format!("{:?}", ps.price_tick_type),
+                    value: ps.price,
+                });
             }
+            TickTypes::SnapshotEnd => break,
+            _ => {}
         }
     }
+    true
 }
 
 fn print_text(data: &OutputData, symbol: &str) {
@@ -0,0 +1,151 @@
+//! `order` subcommand: place a BUY/SELL order and stream fills until done.
+
+use crate::OutputFormat;
+use clap::{Args, ValueEnum};
+use csv::Writer;
+use ibapi::client::blocking::Client;
+use ibapi::orders::{order_builder, Action, OrderUpdate};
+use ibapi::prelude::*;
+use serde::Serialize;
+use std::io;
+use std::time::Duration;
+
+#[derive(Args)]
+pub struct OrderArgs {
+    /// Symbol to trade
+    #[arg(short, long)]
+    pub symbol: String,
+
+    /// BUY or SELL
+    #[arg(long, value_enum)]
+    pub side: Side,
+
+    /// Number of shares
+    #[arg(short, long)]
+    pub quantity: f64,
+
+    /// Order type: market or limit
+    #[arg(long, value_enum, default_value = "market")]
+    pub order_type: OrderKind,
+
+    /// Limit price (required when --order-type limit)
+    #[arg(long)]
+    pub price: Option<f64>,
+
+    /// Seconds to wait for a fill before cancelling any unfilled remainder
+    #[arg(long, default_value_t = 60)]
+    pub timeout: u64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OrderKind {
+    Market,
+    Limit,
+}
+
+#[derive(Serialize)]
+struct OrderFillRow {
+    order_id: i32,
+    status: String,
+    filled: f64,
+    remaining: f64,
+    avg_fill_price: f64,
+}
+
+pub fn run(client: &Client, args: &OrderArgs, format: OutputFormat) {
+    let contract = Contract::stock(&args.symbol).build();
+    let action = match args.side {
+        Side::Buy => Action::Buy,
+        Side::Sell => Action::Sell,
+    };
+
+    let order = match args.order_type {
+        OrderKind::Market => order_builder::market_order(action, args.quantity),
+        OrderKind::Limit => {
+            let price = args.price.unwrap_or_else(|| {
+                eprintln!("--price is required for limit orders");
+                std::process::exit(1);
+            });
+            order_builder::limit_order(action, args.quantity, price)
+        }
+    };
+
+    let order_id = client.next_order_id();
+    let subscription = match client.place_order(order_id, &contract, &order) {
+        Ok(subscription) => subscription,
+        Err(e) => {
+            eprintln!("Failed to place order: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // `for update in &subscription` blocks on the next message, and IBKR only
+    // pushes OrderStatus on submit and on fill/cancel/reject - it may send
+    // nothing at all while an order sits unfilled. A deadline check between
+    // updates would never fire in that case, so enforce --timeout with a
+    // scoped timer thread that cancels the order itself; the resulting
+    // "Cancelled" OrderStatus is what unblocks the loop below. The thread
+    // waits on a channel instead of sleeping outright so a fast fill can wake
+    // it early and let the scope return immediately rather than blocking for
+    // the full timeout regardless of how quickly the order finished.
+    let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            if done_rx.recv_timeout(Duration::from_secs(args.timeout)).is_err() {
+                client.cancel_order(order_id, "");
+            }
+        });
+
+        for update in &subscription {
+            match update {
+                OrderUpdate::OrderStatus(status) => {
+                    emit(
+                        &OrderFillRow {
+                            order_id,
+                            status: status.status.clone(),
+                            filled: status.filled,
+                            remaining: status.remaining,
+                            avg_fill_price: status.average_fill_price,
+                        },
+                        format,
+                    );
+
+                    if status.remaining <= 0.0 || status.status == "Cancelled" {
+                        subscription.cancel();
+                        break;
+                    }
+                }
+                OrderUpdate::ExecutionData(_) | OrderUpdate::CommissionReport(_) => {}
+                OrderUpdate::Error(e) => {
+                    eprintln!("Order error: {}", e);
+                }
+            }
+        }
+
+        let _ = done_tx.send(());
+    });
+}
+
+fn emit(row: &OrderFillRow, format: OutputFormat) {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string(row).unwrap()),
+        OutputFormat::Csv => {
+            let mut wtr = Writer::from_writer(io::stdout());
+            wtr.serialize(row).unwrap();
+            wtr.flush().unwrap();
+        }
+        OutputFormat::Text | OutputFormat::Ledger | OutputFormat::Beancount => {
+            println!(
+                "order {}: {} filled={:.2} remaining={:.2} avg_price={:.2}",
+                row.order_id, row.status, row.filled, row.remaining, row.avg_fill_price
+            );
+        }
+    }
+}
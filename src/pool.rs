@@ -0,0 +1,54 @@
+//! Reconnection-aware client pool backing `--watch` mode: a dropped
+//! TWS/Gateway socket is detected and re-established with backoff rather
+//! than aborting the whole process.
+
+use ibapi::client::blocking::Client;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub struct ClientPool {
+    url: String,
+    client_id: i32,
+    inner: Mutex<Client>,
+}
+
+impl ClientPool {
+    /// Connects, retrying with backoff until the first connection succeeds.
+    pub fn connect(url: &str, client_id: i32) -> Self {
+        let client = connect_with_backoff(url, client_id);
+        Self {
+            url: url.to_string(),
+            client_id,
+            inner: Mutex::new(client),
+        }
+    }
+
+    /// Runs `f` against the current client, holding the pool's lock so
+    /// concurrent collectors don't race on the single underlying socket.
+    pub fn with_client<T>(&self, f: impl FnOnce(&Client) -> T) -> T {
+        let guard = self.inner.lock().unwrap();
+        f(&guard)
+    }
+
+    /// Drops the current client and reconnects with backoff, blocking any
+    /// other caller of `with_client` until a new connection is established.
+    pub fn reconnect(&self) {
+        let mut guard = self.inner.lock().unwrap();
+        eprintln!("Connection to {} lost, reconnecting...", self.url);
+        *guard = connect_with_backoff(&self.url, self.client_id);
+    }
+}
+
+fn connect_with_backoff(url: &str, client_id: i32) -> Client {
+    let mut delay = Duration::from_secs(1);
+    loop {
+        match Client::connect(url, client_id) {
+            Ok(client) => return client,
+            Err(e) => {
+                eprintln!("Connection to {} failed ({}), retrying in {:?}...", url, e, delay);
+                std::thread::sleep(delay);
+                delay = (delay * 2).min(Duration::from_secs(30));
+            }
+        }
+    }
+}
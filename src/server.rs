@@ -0,0 +1,293 @@
+//! `serve` subcommand: keep the IBKR connection open and fan out account,
+//! position, and tick updates to any number of connected WebSocket clients.
+//!
+//! Modeled as checkpoint-plus-delta: each topic keeps a shared, mutex-guarded
+//! latest snapshot. A newly connected peer is sent the current snapshot on
+//! subscribe, then incremental JSON messages as updates arrive.
+
+use crate::{AccountSummaryRow, MarketDataRow, PositionRow};
+use clap::Args;
+use futures_util::{SinkExt, StreamExt};
+use ibapi::accounts::{types::AccountGroup, AccountSummaryResult, AccountSummaryTags, PositionUpdate};
+use ibapi::client::blocking::Client;
+use ibapi::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Port to listen for WebSocket connections on
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Symbol to stream market data for
+    #[arg(short, long, default_value = "AAPL")]
+    pub symbol: String,
+}
+
+#[derive(Deserialize)]
+struct Subscribe {
+    topic: String,
+    symbol: Option<String>,
+}
+
+#[derive(Clone, Default, Serialize)]
+struct Snapshot {
+    account_summary: Vec<AccountSummaryRow>,
+    positions: Vec<PositionRow>,
+    market_data: HashMap<String, Vec<MarketDataRow>>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(tag = "topic")]
+enum Update {
+    #[serde(rename = "account_summary")]
+    AccountSummary { rows: Vec<AccountSummaryRow> },
+    #[serde(rename = "positions")]
+    Positions { rows: Vec<PositionRow> },
+    #[serde(rename = "marketdata")]
+    MarketData { symbol: String, rows: Vec<MarketDataRow> },
+}
+
+type Tx = mpsc::UnboundedSender<Message>;
+type PeerMap = Arc<Mutex<HashMap<SocketAddr, Tx>>>;
+type SharedSnapshot = Arc<Mutex<Snapshot>>;
+
+pub fn run(client: Client, args: &ServeArgs) {
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start async runtime");
+    runtime.block_on(serve(client, args));
+}
+
+async fn serve(client: Client, args: &ServeArgs) {
+    let addr = format!("0.0.0.0:{}", args.port);
+    let listener = TcpListener::bind(&addr)
+        .await
+        .unwrap_or_else(|e| panic!("failed to bind {}: {}", addr, e));
+    eprintln!("Serving live updates on ws://{}", addr);
+
+    let peers: PeerMap = Arc::new(Mutex::new(HashMap::new()));
+    let snapshot: SharedSnapshot = Arc::new(Mutex::new(Snapshot::default()));
+    let client = Arc::new(client);
+    let (tx, mut rx) = mpsc::unbounded_channel::<Update>();
+
+    spawn_pollers(client, args.symbol.clone(), tx);
+
+    {
+        let peers = peers.clone();
+        let snapshot = snapshot.clone();
+        tokio::spawn(async move {
+            while let Some(update) = rx.recv().await {
+                apply_update(&snapshot, &update).await;
+                broadcast(&peers, &update).await;
+            }
+        });
+    }
+
+    while let Ok((stream, addr)) = listener.accept().await {
+        tokio::spawn(handle_connection(stream, addr, peers.clone(), snapshot.clone()));
+    }
+}
+
+/// Each topic gets its own blocking thread since `Client` subscriptions block
+/// on their own iterator until cancelled; updates are forwarded into the
+/// async side over an unbounded channel as they arrive.
+fn spawn_pollers(client: Arc<Client>, symbol: String, tx: mpsc::UnboundedSender<Update>) {
+    {
+        let client = client.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || stream_account_summary(&client, tx));
+    }
+    {
+        let client = client.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || stream_positions(&client, tx));
+    }
+    std::thread::spawn(move || stream_market_data(&client, &symbol, tx));
+}
+
+/// Streams account-summary changes for the lifetime of the process. `End`
+/// only marks the initial snapshot as complete - the subscription itself
+/// stays open and keeps pushing `Summary` rows as account values change, so
+/// it must not be cancelled there.
+fn stream_account_summary(client: &Client, tx: mpsc::UnboundedSender<Update>) {
+    let tags = &[
+        AccountSummaryTags::ACCOUNT_TYPE,
+        AccountSummaryTags::NET_LIQUIDATION,
+        AccountSummaryTags::TOTAL_CASH_VALUE,
+        AccountSummaryTags::BUYING_POWER,
+        AccountSummaryTags::GROSS_POSITION_VALUE,
+        AccountSummaryTags::AVAILABLE_FUNDS,
+    ];
+
+    let Ok(subscription) = client.account_summary(&AccountGroup("All".to_string()), tags) else {
+        return;
+    };
+
+    let mut rows: Vec<AccountSummaryRow> = Vec::new();
+    for update in &subscription {
+        match update {
+            AccountSummaryResult::Summary(summary) => {
+                let row = AccountSummaryRow {
+                    account: summary.account.clone(),
+                    tag: summary.tag.clone(),
+                    value: summary.value.clone(),
+                    currency: summary.currency.clone(),
+                };
+                upsert_by(&mut rows, row, |r| (r.account.clone(), r.tag.clone()));
+                let _ = tx.send(Update::AccountSummary { rows: rows.clone() });
+            }
+            AccountSummaryResult::End => {}
+        }
+    }
+}
+
+/// Streams position changes for the lifetime of the process; `PositionEnd`
+/// likewise only marks the initial snapshot, not the end of the subscription.
+fn stream_positions(client: &Client, tx: mpsc::UnboundedSender<Update>) {
+    let Ok(positions) = client.positions() else {
+        return;
+    };
+
+    let mut rows: Vec<PositionRow> = Vec::new();
+    while let Some(update) = positions.next() {
+        match update {
+            PositionUpdate::Position(pos) => {
+                let row = PositionRow {
+                    account: pos.account.clone(),
+                    symbol: pos.contract.symbol.to_string(),
+                    position: pos.position,
+                    average_cost: pos.average_cost,
+                    market_value: pos.position * pos.average_cost,
+                };
+                upsert_by(&mut rows, row, |r| (r.account.clone(), r.symbol.clone()));
+                let _ = tx.send(Update::Positions { rows: rows.clone() });
+            }
+            PositionUpdate::PositionEnd => {}
+        }
+    }
+}
+
+/// Streams live ticks for `symbol` for the lifetime of the process.
+fn stream_market_data(client: &Client, symbol: &str, tx: mpsc::UnboundedSender<Update>) {
+    let contract = Contract::stock(symbol).build();
+    let Ok(subscription) = client.market_data(&contract).subscribe() else {
+        return;
+    };
+
+    let mut rows: Vec<MarketDataRow> = Vec::new();
+    for tick in &subscription {
+        let row = match tick {
+            TickTypes::Price(price) => Some(MarketDataRow {
+                symbol: symbol.to_string(),
+                tick_type: format!("{:?}", price.tick_type),
+                value: price.price,
+            }),
+            TickTypes::Size(size) => Some(MarketDataRow {
+                symbol: symbol.to_string(),
+                tick_type: format!("{:?}", size.tick_type),
+                value: size.size,
+            }),
+            TickTypes::PriceSize(ps) => Some(MarketDataRow {
+                symbol: symbol.to_string(),
+                tick_type: format!("{:?}", ps.price_tick_type),
+                value: ps.price,
+            }),
+            _ => None,
+        };
+
+        if let Some(row) = row {
+            upsert_by(&mut rows, row, |r| r.tick_type.clone());
+            let _ = tx.send(Update::MarketData {
+                symbol: symbol.to_string(),
+                rows: rows.clone(),
+            });
+        }
+    }
+}
+
+/// Replaces the row whose key (via `key_fn`) matches `row`'s, or appends it.
+fn upsert_by<T, K: PartialEq>(rows: &mut Vec<T>, row: T, key_fn: impl Fn(&T) -> K) {
+    let key = key_fn(&row);
+    match rows.iter_mut().find(|existing| key_fn(existing) == key) {
+        Some(existing) => *existing = row,
+        None => rows.push(row),
+    }
+}
+
+async fn apply_update(snapshot: &SharedSnapshot, update: &Update) {
+    let mut snapshot = snapshot.lock().await;
+    match update {
+        Update::AccountSummary { rows } => snapshot.account_summary = rows.clone(),
+        Update::Positions { rows } => snapshot.positions = rows.clone(),
+        Update::MarketData { symbol, rows } => {
+            snapshot.market_data.insert(symbol.clone(), rows.clone());
+        }
+    }
+}
+
+async fn broadcast(peers: &PeerMap, update: &Update) {
+    let message = Message::Text(serde_json::to_string(update).unwrap());
+    let peers = peers.lock().await;
+    for tx in peers.values() {
+        let _ = tx.send(message.clone());
+    }
+}
+
+async fn handle_connection(stream: TcpStream, addr: SocketAddr, peers: PeerMap, snapshot: SharedSnapshot) {
+    let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(e) => {
+            eprintln!("WebSocket handshake with {} failed: {}", addr, e);
+            return;
+        }
+    };
+    let (mut outgoing, mut incoming) = ws_stream.split();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    peers.lock().await.insert(addr, tx);
+
+    let forward = tokio::spawn(async move {
+        while let Some(message) = rx.recv().await {
+            if outgoing.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = incoming.next().await {
+        if let Message::Text(text) = message {
+            if let Ok(subscribe) = serde_json::from_str::<Subscribe>(&text) {
+                send_snapshot(&snapshot, &subscribe, &peers, addr).await;
+            }
+        }
+    }
+
+    peers.lock().await.remove(&addr);
+    forward.abort();
+}
+
+async fn send_snapshot(snapshot: &SharedSnapshot, subscribe: &Subscribe, peers: &PeerMap, addr: SocketAddr) {
+    let snapshot = snapshot.lock().await;
+    let update = match subscribe.topic.as_str() {
+        "account_summary" => Update::AccountSummary {
+            rows: snapshot.account_summary.clone(),
+        },
+        "positions" => Update::Positions {
+            rows: snapshot.positions.clone(),
+        },
+        "marketdata" => {
+            let symbol = subscribe.symbol.clone().unwrap_or_default();
+            let rows = snapshot.market_data.get(&symbol).cloned().unwrap_or_default();
+            Update::MarketData { symbol, rows }
+        }
+        _ => return,
+    };
+
+    if let Some(tx) = peers.lock().await.get(&addr) {
+        let _ = tx.send(Message::Text(serde_json::to_string(&update).unwrap()));
+    }
+}
@@ -0,0 +1,52 @@
+//! `--watch INTERVAL` mode: re-collect account summary, positions, and
+//! market data on a timer instead of connecting once and exiting.
+
+use crate::pool::ClientPool;
+use crate::{
+    collect_account_summary, collect_market_data, collect_positions, print_csv, print_text,
+    OutputData, OutputFormat,
+};
+use std::time::Duration;
+
+pub fn run(
+    pool: &ClientPool,
+    symbol: &str,
+    no_market_data: bool,
+    format: OutputFormat,
+    interval_secs: u64,
+) -> ! {
+    loop {
+        let mut data = OutputData {
+            account_summary: Vec::new(),
+            positions: Vec::new(),
+            market_data: Vec::new(),
+        };
+
+        let summary_ok = pool.with_client(|client| {
+            collect_account_summary(client, &mut data.account_summary)
+        });
+        let positions_ok = pool.with_client(|client| collect_positions(client, &mut data.positions));
+        let market_data_ok = if no_market_data {
+            true
+        } else {
+            pool.with_client(|client| collect_market_data(client, symbol, &mut data.market_data))
+        };
+
+        // Each collector reports whether its subscription was ever
+        // established; a dropped socket can fail any one of them
+        // independently of the others, so check all three rather than just
+        // inferring a drop from an empty account summary.
+        if !summary_ok || !positions_ok || !market_data_ok {
+            pool.reconnect();
+        }
+
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string(&data).unwrap()),
+            OutputFormat::Csv => print_csv(&data),
+            OutputFormat::Text => print_text(&data, symbol),
+            OutputFormat::Ledger | OutputFormat::Beancount => {}
+        }
+
+        std::thread::sleep(Duration::from_secs(interval_secs));
+    }
+}